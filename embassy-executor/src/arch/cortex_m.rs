@@ -7,6 +7,7 @@ mod thread {
 
     #[cfg(feature = "nightly")]
     pub use embassy_macros::main_cortex_m as main;
+    #[cfg(feature = "softdevice")]
     use nrf_softdevice_s132::sd_app_evt_wait;
 
     use crate::raw::{Pender, PenderInner};
@@ -21,6 +22,50 @@ mod thread {
         }
     }
 
+    /// Low-power sleep primitive used by the thread-mode [`Executor`] when it has no more
+    /// work to do.
+    ///
+    /// Implement this to plug the executor into whatever mechanism your chip or firmware
+    /// uses to reach a low-power state.
+    pub trait Idle {
+        /// Sleep until the next interrupt or event wakes the processor up.
+        fn wait(&self);
+    }
+
+    /// [`Idle`] impl that calls the nRF SoftDevice's `sd_app_evt_wait()`.
+    ///
+    /// This is the right choice when the S132 SoftDevice is enabled: it puts the chip to
+    /// sleep while still letting the SoftDevice service its own radio timing.
+    #[cfg(feature = "softdevice")]
+    #[derive(Copy, Clone, Default)]
+    pub struct SoftdeviceWait;
+
+    #[cfg(feature = "softdevice")]
+    impl Idle for SoftdeviceWait {
+        fn wait(&self) {
+            unsafe { sd_app_evt_wait() };
+        }
+    }
+
+    /// [`Idle`] impl that emits a plain `WFE` instruction.
+    ///
+    /// This is the right choice for chips that aren't running the nRF SoftDevice: `WFE`
+    /// triggers low-power sleep directly, and is woken up again by the `SEV` that
+    /// [`ThreadPender::pend`] executes.
+    #[derive(Copy, Clone, Default)]
+    pub struct WfeWait;
+
+    impl Idle for WfeWait {
+        fn wait(&self) {
+            unsafe { asm!("wfe") };
+        }
+    }
+
+    #[cfg(feature = "softdevice")]
+    type DefaultIdle = SoftdeviceWait;
+    #[cfg(not(feature = "softdevice"))]
+    type DefaultIdle = WfeWait;
+
     /// Thread mode executor, using WFE/SEV.
     ///
     /// This is the simplest and most common kind of executor. It runs on
@@ -31,16 +76,31 @@ mod thread {
     /// This executor allows for ultra low power consumption for chips where `WFE`
     /// triggers low-power sleep without extra steps. If your chip requires extra steps,
     /// you may use [`raw::Executor`] directly to program custom behavior.
-    pub struct Executor {
+    ///
+    /// The sleep primitive used between polls is given by the `I: `[`Idle`] type parameter.
+    /// By default this is [`SoftdeviceWait`] when the `softdevice` feature is enabled, and
+    /// [`WfeWait`] otherwise; use [`new_with_idle`](Self::new_with_idle) to plug in a
+    /// different one.
+    pub struct Executor<I: Idle = DefaultIdle> {
         inner: raw::Executor,
+        idle: I,
         not_send: PhantomData<*mut ()>,
     }
 
-    impl Executor {
-        /// Create a new Executor.
+    impl Executor<DefaultIdle> {
+        /// Create a new Executor, using the default [`Idle`] primitive for this target.
         pub fn new() -> Self {
+            Self::new_with_idle(Default::default())
+        }
+    }
+
+    impl<I: Idle> Executor<I> {
+        /// Create a new Executor, sleeping with the given [`Idle`] primitive when it has no
+        /// more work to do.
+        pub fn new_with_idle(idle: I) -> Self {
             Self {
                 inner: raw::Executor::new(Pender(PenderInner::Thread(ThreadPender))),
+                idle,
                 not_send: PhantomData,
             }
         }
@@ -67,10 +127,108 @@ mod thread {
             init(self.inner.spawner());
 
             loop {
-                unsafe {
-                    self.inner.poll();
-                    sd_app_evt_wait();
-                };
+                unsafe { self.inner.poll() };
+                self.idle.wait();
+            }
+        }
+
+        /// Start the executor.
+        ///
+        /// Unlike [`run`](Self::run), this spawns the initial task(s) and returns instead of
+        /// looping forever. This gives you back control of `fn main() -> !`, so you can drive
+        /// your own main loop and call [`poll_and_wait`](Self::poll_and_wait) from it, for
+        /// example interleaved with a hand-written SoftDevice event dispatch loop.
+        ///
+        /// The `init` closure is called with a [`Spawner`] that spawns tasks on this executor,
+        /// exactly like in [`run`](Self::run).
+        ///
+        /// This function requires `&'static mut self`, see [`run`](Self::run) for the reasons
+        /// and ways to obtain it. It returns a shared `&'static` reference to the executor,
+        /// which is what you call [`poll_and_wait`](Self::poll_and_wait) on afterwards.
+        pub fn start(&'static mut self, init: impl FnOnce(Spawner)) -> &'static Self {
+            init(self.inner.spawner());
+            self
+        }
+
+        /// Poll all tasks once, then sleep until the next one is woken.
+        ///
+        /// This runs a single iteration of the loop that [`run`](Self::run) would otherwise run
+        /// forever: it polls the executor, then sleeps with this executor's [`Idle`] primitive
+        /// until the next task wakes it up again. Call this repeatedly from your own main loop
+        /// after [`start`](Self::start), interleaved with whatever else needs to run on thread
+        /// mode.
+        pub fn poll_and_wait(&self) {
+            unsafe { self.inner.poll() };
+            self.idle.wait();
+        }
+    }
+}
+
+#[cfg(feature = "pender-callback")]
+pub use callback::*;
+#[cfg(feature = "pender-callback")]
+mod callback {
+    use core::marker::PhantomData;
+
+    use crate::raw::{Pender, PenderInner};
+    use crate::{raw, Spawner};
+
+    /// Callback-driven executor.
+    ///
+    /// This executor doesn't assume anything about how the chip sleeps or wakes up. Instead,
+    /// it invokes a user-supplied `pend_fn` callback whenever a task is woken, and lets the
+    /// caller drive sleeping with the `idle` closure passed to [`run`](Self::run). This is
+    /// useful for chips that don't run the nRF SoftDevice and can't just use `WFE`, for example
+    /// when the executor has to be integrated into an existing RTOS or hand-rolled low-power
+    /// state machine.
+    pub struct CallbackExecutor {
+        inner: raw::Executor,
+        not_send: PhantomData<*mut ()>,
+    }
+
+    impl CallbackExecutor {
+        /// Create a new CallbackExecutor.
+        ///
+        /// `pend_fn` is called whenever a task is woken and the executor needs polling again.
+        /// It is typically used to wake up whatever mechanism the `idle` closure passed to
+        /// [`run`](Self::run) uses to sleep. `context` is passed through to `pend_fn` unchanged,
+        /// so it can carry whatever state `pend_fn` needs to do its job.
+        pub fn new(pend_fn: fn(*mut ()), context: *mut ()) -> Self {
+            Self {
+                inner: raw::Executor::new(Pender(PenderInner::Callback {
+                    func: pend_fn,
+                    context,
+                })),
+                not_send: PhantomData,
+            }
+        }
+
+        /// Run the executor.
+        ///
+        /// The `init` closure is called with a [`Spawner`] that spawns tasks on this executor.
+        /// Use it to spawn the initial task(s). After `init` returns, the executor starts
+        /// running the tasks.
+        ///
+        /// `idle` is called every time the executor has no more work to do. Use it to put the
+        /// chip to sleep with whatever mechanism is appropriate for your target (plain `WFE`,
+        /// entering a low-power mode, yielding to an RTOS, ...). It is woken up again through
+        /// `pend_fn`.
+        ///
+        /// This function requires `&'static mut self`. This means you have to store the
+        /// Executor instance in a place where it'll live forever and grants you mutable
+        /// access. There's a few ways to do this:
+        ///
+        /// - a [StaticCell](https://docs.rs/static_cell/latest/static_cell/) (safe)
+        /// - a `static mut` (unsafe)
+        /// - a local variable in a function you know never returns (like `fn main() -> !`), upgrading its lifetime with `transmute`. (unsafe)
+        ///
+        /// This function never returns.
+        pub fn run(&'static mut self, mut idle: impl FnMut(), init: impl FnOnce(Spawner)) -> ! {
+            init(self.inner.spawner());
+
+            loop {
+                unsafe { self.inner.poll() };
+                idle();
             }
         }
     }
@@ -136,6 +294,8 @@ mod interrupt {
     pub struct InterruptExecutor {
         started: AtomicBool,
         executor: UnsafeCell<MaybeUninit<raw::Executor>>,
+        pender: UnsafeCell<MaybeUninit<InterruptPender>>,
+        idle: UnsafeCell<Option<fn()>>,
     }
 
     unsafe impl Send for InterruptExecutor {}
@@ -148,6 +308,8 @@ mod interrupt {
             Self {
                 started: AtomicBool::new(false),
                 executor: UnsafeCell::new(MaybeUninit::uninit()),
+                pender: UnsafeCell::new(MaybeUninit::uninit()),
+                idle: UnsafeCell::new(None),
             }
         }
 
@@ -184,6 +346,22 @@ mod interrupt {
         /// do it after.
         ///
         pub fn start(&'static self, irq: impl InterruptNumber) -> crate::SendSpawner {
+            self.start_internal(irq, None)
+        }
+
+        /// Start the executor, with an idle callback for the paired thread-mode loop.
+        ///
+        /// This works exactly like [`start`](Self::start), except `idle` is stashed away and
+        /// can later be invoked through [`idle`](Self::idle) from whatever thread-mode loop
+        /// supervises this executor. This is meant to be paired with [`pended`](Self::pended):
+        /// the thread loop checks [`pended`](Self::pended) to see if this executor still has
+        /// queued work, and if not, calls [`idle`](Self::idle) (which in turn may put the chip
+        /// to sleep) instead of spuriously waking up.
+        pub fn start_with_idle(&'static self, irq: impl InterruptNumber, idle: fn()) -> crate::SendSpawner {
+            self.start_internal(irq, Some(idle))
+        }
+
+        fn start_internal(&'static self, irq: impl InterruptNumber, idle: Option<fn()>) -> crate::SendSpawner {
             if self
                 .started
                 .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
@@ -192,12 +370,14 @@ mod interrupt {
                 panic!("InterruptExecutor::start() called multiple times on the same executor.");
             }
 
+            let pender = InterruptPender(irq.number());
+
             unsafe {
                 (&mut *self.executor.get())
                     .as_mut_ptr()
-                    .write(raw::Executor::new(Pender(PenderInner::Interrupt(InterruptPender(
-                        irq.number(),
-                    )))))
+                    .write(raw::Executor::new(Pender(PenderInner::Interrupt(pender))));
+                (&mut *self.pender.get()).as_mut_ptr().write(pender);
+                *self.idle.get() = idle;
             }
 
             let executor = unsafe { (&*self.executor.get()).assume_init_ref() };
@@ -207,6 +387,38 @@ mod interrupt {
             executor.spawner().make_send()
         }
 
+        /// Check whether this executor still has queued work.
+        ///
+        /// This is true while the paired interrupt is pending, i.e. it has been woken but
+        /// [`on_interrupt`](Self::on_interrupt) hasn't run (or has run and woken something else)
+        /// yet. A supervising thread-mode loop can use this to decide whether it's safe to go
+        /// to sleep, or whether this executor still needs to be polled first.
+        ///
+        /// This MUST only be called on an executor that has already been started.
+        /// The function will panic otherwise.
+        pub fn pended(&'static self) -> bool {
+            if !self.started.load(Ordering::Acquire) {
+                panic!("InterruptExecutor::pended() called on uninitialized executor.");
+            }
+            let pender = unsafe { (&*self.pender.get()).assume_init_ref() };
+            NVIC::is_pending(*pender)
+        }
+
+        /// Alias for [`pended`](Self::pended).
+        pub fn is_pending(&'static self) -> bool {
+            self.pended()
+        }
+
+        /// Invoke the idle callback registered with [`start_with_idle`](Self::start_with_idle),
+        /// if any.
+        ///
+        /// This does nothing if the executor was started with [`start`](Self::start) instead.
+        pub fn idle(&'static self) {
+            if let Some(idle) = unsafe { *self.idle.get() } {
+                idle();
+            }
+        }
+
         /// Get a SendSpawner for this executor
         ///
         /// This returns a [`SendSpawner`] you can use to spawn tasks on this